@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+
+use rstar::{RTree, RTreeObject, AABB};
+
+use crate::distance::{haversine_km, KM_PER_DEGREE_LAT};
+
+/// A point carried in the R-tree, tagged with its position in the original input.
+struct IndexedPoint {
+    id: usize,
+    lat: f64,
+    lon: f64,
+}
+
+impl RTreeObject for IndexedPoint {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        AABB::from_point([self.lat, self.lon])
+    }
+}
+
+/// Runs DBSCAN over `points` using an R-tree for region queries instead of a brute-force
+/// O(n^2) scan, so clustering stays fast on datasets with millions of points.
+///
+/// Returns one cluster id per input point, in order; `-1` marks noise.
+pub fn cluster(points: &[(f64, f64)], epsilon_km: f64, min_samples: usize) -> Vec<i32> {
+    let n = points.len();
+    if n < 2 {
+        return vec![-1; n];
+    }
+
+    let tree = RTree::bulk_load(
+        points
+            .iter()
+            .enumerate()
+            .map(|(id, &(lat, lon))| IndexedPoint { id, lat, lon })
+            .collect(),
+    );
+
+    let mut labels = vec![-1i32; n];
+    let mut visited = vec![false; n];
+    let mut next_cluster_id = 0i32;
+
+    // The tree's internal node pruning compares plain Euclidean distance over raw
+    // (lat, lon) degrees, which doesn't share units with a kilometer epsilon. Rather than
+    // fight that by making `distance_2` lie about the metric, over-fetch a degree-space
+    // bounding box guaranteed to contain the epsilon-km circle, then confirm each
+    // candidate with the real haversine distance. The longitude bound is taken at the
+    // box's most poleward latitude, where a kilometer spans the most degrees of longitude;
+    // a circle that reaches a pole or crosses the antimeridian wraps around, so it gets
+    // the full longitude range instead.
+    let region_query = |lat: f64, lon: f64| -> Vec<usize> {
+        let lat_delta_deg = epsilon_km / KM_PER_DEGREE_LAT;
+        let max_abs_lat = lat.abs() + lat_delta_deg;
+        let lon_delta_deg = if max_abs_lat >= 90.0 {
+            180.0
+        } else {
+            epsilon_km / (KM_PER_DEGREE_LAT * max_abs_lat.to_radians().cos())
+        };
+        let wraps = lon - lon_delta_deg < -180.0 || lon + lon_delta_deg > 180.0;
+        let (lon_min, lon_max) = if wraps { (-180.0, 180.0) } else { (lon - lon_delta_deg, lon + lon_delta_deg) };
+
+        let envelope = AABB::from_corners(
+            [lat - lat_delta_deg, lon_min],
+            [lat + lat_delta_deg, lon_max],
+        );
+
+        tree.locate_in_envelope(&envelope)
+            .filter(|p| haversine_km(p.lat, p.lon, lat, lon) <= epsilon_km)
+            .map(|p| p.id)
+            .collect()
+    };
+
+    for i in 0..n {
+        if visited[i] {
+            continue;
+        }
+        visited[i] = true;
+
+        let (lat, lon) = points[i];
+        let neighbors = region_query(lat, lon);
+        if neighbors.len() < min_samples {
+            continue; // not a core point; may still be claimed as a border point later
+        }
+
+        let cluster_id = next_cluster_id;
+        next_cluster_id += 1;
+        labels[i] = cluster_id;
+
+        let mut queue: VecDeque<usize> = neighbors.into_iter().collect();
+        while let Some(j) = queue.pop_front() {
+            if labels[j] == -1 {
+                labels[j] = cluster_id;
+            }
+
+            if visited[j] {
+                continue;
+            }
+            visited[j] = true;
+
+            let (jlat, jlon) = points[j];
+            let j_neighbors = region_query(jlat, jlon);
+            if j_neighbors.len() >= min_samples {
+                queue.extend(j_neighbors);
+            }
+        }
+    }
+
+    labels
+}
@@ -0,0 +1,26 @@
+use anyhow::{Context, Result};
+use linfa::traits::{Fit, Predict};
+use linfa::Dataset;
+use linfa_clustering::KMeans;
+use ndarray::{Array1, Array2};
+
+/// Runs KMeans over `points` and returns each point's assigned centroid index, in order.
+pub fn cluster(points: &[(f64, f64)], k: usize) -> Result<Vec<i32>> {
+    let n = points.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    let array: Array2<f64> = Array2::from_shape_vec(
+        (n, 2),
+        points.iter().flat_map(|&(lat, lon)| [lat, lon]).collect(),
+    )
+    .context("Failed to build point matrix for KMeans")?;
+    let dataset = Dataset::new(array, Array1::<usize>::zeros(n));
+
+    let model = KMeans::params(k)
+        .fit(&dataset)
+        .context("KMeans failed to converge")?;
+
+    Ok(model.predict(dataset.records()).iter().map(|&c| c as i32).collect())
+}
@@ -0,0 +1,42 @@
+mod dbscan;
+mod kmeans;
+mod optics;
+
+use anyhow::Result;
+use clap::ValueEnum;
+
+/// Which clustering estimator to run over the input points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Algorithm {
+    Dbscan,
+    Optics,
+    Kmeans,
+}
+
+impl Algorithm {
+    pub fn label(self) -> &'static str {
+        match self {
+            Algorithm::Dbscan => "DBSCAN",
+            Algorithm::Optics => "OPTICS",
+            Algorithm::Kmeans => "KMeans",
+        }
+    }
+}
+
+/// Dispatches to the configured estimator and returns one cluster id per input point, in
+/// order. `-1` marks noise where the algorithm supports it; the CSV `cluster` column
+/// semantics are otherwise unchanged regardless of which algorithm produced them.
+pub fn perform_clustering(
+    points: &[(f64, f64)],
+    algorithm: Algorithm,
+    epsilon_km: f64,
+    min_samples: usize,
+    max_epsilon_km: Option<f64>,
+    k: usize,
+) -> Result<Vec<i32>> {
+    match algorithm {
+        Algorithm::Dbscan => Ok(dbscan::cluster(points, epsilon_km, min_samples)),
+        Algorithm::Optics => optics::cluster(points, min_samples, max_epsilon_km),
+        Algorithm::Kmeans => kmeans::cluster(points, k),
+    }
+}
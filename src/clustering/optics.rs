@@ -0,0 +1,124 @@
+use anyhow::{Context, Result};
+use linfa::{prelude::Transformer, Dataset};
+use linfa_clustering::Optics;
+use ndarray::{Array1, Array2};
+
+use crate::distance::HaversineDistance;
+
+/// Runs OPTICS over `points` and flattens its reachability plot into cluster ids.
+///
+/// Returns one cluster id per input point, in order; `-1` marks noise.
+pub fn cluster(points: &[(f64, f64)], min_samples: usize, max_epsilon_km: Option<f64>) -> Result<Vec<i32>> {
+    let n = points.len();
+    if n < 2 {
+        return Ok(vec![-1; n]);
+    }
+
+    let array: Array2<f64> = Array2::from_shape_vec(
+        (n, 2),
+        points.iter().flat_map(|&(lat, lon)| [lat, lon]).collect(),
+    )
+    .context("Failed to build point matrix for OPTICS")?;
+    let dataset = Dataset::new(array, Array1::<usize>::zeros(n));
+
+    let mut params = Optics::params(min_samples).dist_fn(HaversineDistance);
+    if let Some(max_epsilon_km) = max_epsilon_km {
+        params = params.tolerance(max_epsilon_km);
+    }
+
+    let analysis = params.transform(&dataset)?;
+
+    let ordered: Vec<(usize, Option<f64>, Option<f64>)> = analysis
+        .iter()
+        .map(|sample| {
+            (
+                sample.index(),
+                sample.reachability_distance().copied(),
+                sample.core_distance().copied(),
+            )
+        })
+        .collect();
+
+    Ok(extract_clusters(&ordered, n, max_epsilon_km))
+}
+
+/// Flattens an OPTICS cluster ordering into cluster ids via the standard `ExtractDBSCAN`
+/// pass, kept separate from the OPTICS computation itself so it can be tested against a
+/// fixed sequence of reachability/core distances.
+///
+/// `ordered` holds `(original_index, reachability_distance, core_distance)` triples in
+/// cluster-ordering order. A point outside the current cluster (reachability distance
+/// undefined or beyond `max_epsilon_km`) opens a new cluster when it is itself a core
+/// point (core distance within `max_epsilon_km`); otherwise only that point is noise and
+/// the currently open cluster carries over unchanged to whatever point follows it. With no
+/// `max_epsilon_km`, eps is treated as unbounded, so only points with no core distance at
+/// all (too few neighbors to ever anchor a cluster) end up as noise.
+fn extract_clusters(ordered: &[(usize, Option<f64>, Option<f64>)], n: usize, max_epsilon_km: Option<f64>) -> Vec<i32> {
+    let mut labels = vec![-1i32; n];
+    let mut next_cluster_id = -1i32;
+    let mut current_cluster_id = -1i32;
+
+    for &(index, reachability, core) in ordered {
+        let outside_current_cluster = match (reachability, max_epsilon_km) {
+            (Some(r), Some(eps)) => r > eps,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+
+        if outside_current_cluster {
+            let is_seed = match (core, max_epsilon_km) {
+                (Some(c), Some(eps)) => c <= eps,
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+
+            if !is_seed {
+                // Only this point is noise; the last-started cluster still applies to
+                // whatever reachable point comes next in the ordering.
+                labels[index] = -1;
+                continue;
+            }
+
+            next_cluster_id += 1;
+            current_cluster_id = next_cluster_id;
+        }
+
+        labels[index] = current_cluster_id;
+    }
+
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn noise_point_does_not_reset_the_open_cluster() {
+        // point 0 seeds a cluster, point 1 stays in it, point 2 is a one-off noise point
+        // whose own core distance is too large, and point 3 is reachable again and must
+        // rejoin cluster 0 rather than leak into `-1`.
+        let ordered = vec![
+            (0, None, Some(1.0)),
+            (1, Some(0.5), Some(0.5)),
+            (2, Some(5.0), Some(10.0)),
+            (3, Some(0.5), Some(0.5)),
+        ];
+
+        assert_eq!(extract_clusters(&ordered, 4, Some(1.0)), vec![0, 0, -1, 0]);
+    }
+
+    #[test]
+    fn unbounded_epsilon_only_leaves_coreless_points_as_noise() {
+        let ordered = vec![(0, None, Some(1.0)), (1, Some(0.5), Some(0.5)), (2, None, None)];
+
+        assert_eq!(extract_clusters(&ordered, 3, None), vec![0, 0, -1]);
+    }
+
+    #[test]
+    fn first_point_seeds_a_cluster_when_it_is_a_core_point() {
+        let ordered = vec![(0, None, Some(0.2))];
+
+        assert_eq!(extract_clusters(&ordered, 1, Some(1.0)), vec![0]);
+    }
+}
@@ -0,0 +1,72 @@
+use linfa_nn::distance::Distance;
+use ndarray::ArrayView1;
+
+/// Earth's mean radius in kilometers, used to turn the haversine angle into a real distance.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Kilometers per degree of latitude (constant everywhere, unlike longitude). Useful for
+/// turning a kilometer radius into a degree-space bound for spatial-index queries.
+pub const KM_PER_DEGREE_LAT: f64 = EARTH_RADIUS_KM * std::f64::consts::PI / 180.0;
+
+/// Great-circle distance between two `(lat, lon)` points, in degrees, returned in kilometers.
+///
+/// A plain Euclidean metric over raw degrees treats longitude as if it had the
+/// same physical length everywhere, which is only true at the equator. This
+/// accounts for that convergence so an epsilon in kilometers means the same
+/// thing near the poles as it does near the equator.
+pub fn haversine_km(lat1: f64, lon1: f64, lat2: f64, lon2: f64) -> f64 {
+    let (lat1, lon1) = (lat1.to_radians(), lon1.to_radians());
+    let (lat2, lon2) = (lat2.to_radians(), lon2.to_radians());
+
+    let dlat = lat2 - lat1;
+    let dlon = lon2 - lon1;
+
+    let h = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+
+    // Float error can push `h.sqrt()` a hair past 1.0 for near-antipodal points, which
+    // would otherwise turn `asin` into NaN and poison anything downstream (e.g. Dijkstra).
+    2.0 * EARTH_RADIUS_KM * h.sqrt().min(1.0).asin()
+}
+
+/// Adapts [`haversine_km`] to linfa_nn's `Distance` trait so linfa-based estimators
+/// (e.g. OPTICS) measure density in real kilometers, same as the R-tree DBSCAN path.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HaversineDistance;
+
+impl Distance<f64> for HaversineDistance {
+    fn distance(&self, a: ArrayView1<f64>, b: ArrayView1<f64>) -> f64 {
+        haversine_km(a[0], a[1], b[0], b[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_point_is_zero_distance() {
+        assert_eq!(haversine_km(40.0, -73.0, 40.0, -73.0), 0.0);
+    }
+
+    #[test]
+    fn quarter_circumference_along_the_equator() {
+        let d = haversine_km(0.0, 0.0, 0.0, 90.0);
+        let expected = EARTH_RADIUS_KM * std::f64::consts::FRAC_PI_2;
+        assert!((d - expected).abs() < 1e-6, "got {d}, expected {expected}");
+    }
+
+    #[test]
+    fn antipodal_points_are_half_the_circumference_not_nan() {
+        let d = haversine_km(0.0, 0.0, 0.0, 180.0);
+        let expected = EARTH_RADIUS_KM * std::f64::consts::PI;
+        assert!(!d.is_nan());
+        assert!((d - expected).abs() < 1e-6, "got {d}, expected {expected}");
+    }
+
+    #[test]
+    fn near_antipodal_float_error_does_not_produce_nan() {
+        // Near-antipodal: `h` evaluates to exactly 1.0 here, the edge of `asin`'s domain.
+        let d = haversine_km(0.0, 0.0, 0.000_000_1, 180.0);
+        assert!(!d.is_nan());
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+/// Writes clustering results as a GeoJSON `FeatureCollection`: each row becomes a `Point`
+/// feature carrying its original attributes plus the assigned `cluster` id and a
+/// deterministic per-cluster `color`, so results can be dropped straight onto a web map.
+pub fn write_geojson(
+    output_path: &Path,
+    locations: &[(f64, f64, HashMap<String, String>)],
+    clusters: &[i32],
+) -> Result<()> {
+    let features: Vec<Value> = locations
+        .iter()
+        .zip(clusters.iter())
+        .map(|((lat, lon, extra), &cluster)| {
+            let mut properties = Map::new();
+            for (key, value) in extra {
+                properties.insert(key.clone(), Value::String(value.clone()));
+            }
+            properties.insert("cluster".to_string(), json!(cluster));
+            properties.insert("color".to_string(), json!(cluster_color(cluster)));
+
+            json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": [lon, lat],
+                },
+                "properties": properties,
+            })
+        })
+        .collect();
+
+    let collection = json!({
+        "type": "FeatureCollection",
+        "features": features,
+    });
+
+    let file = File::create(output_path)?;
+    serde_json::to_writer_pretty(file, &collection)?;
+    Ok(())
+}
+
+/// Deterministically assigns a cluster a distinct color by hashing its id into an HSV hue
+/// and converting to hex. Noise (`-1`) always renders gray.
+fn cluster_color(cluster: i32) -> String {
+    if cluster < 0 {
+        return "#808080".to_string();
+    }
+
+    let hue = ((cluster as u64).wrapping_mul(2654435761) % 360) as f64;
+    let (r, g, b) = hsv_to_rgb(hue, 0.65, 0.95);
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Converts an HSV color (hue in degrees, saturation/value in `0.0..=1.0`) to 8-bit RGB.
+fn hsv_to_rgb(h: f64, s: f64, v: f64) -> (u8, u8, u8) {
+    let c = v * s;
+    let h_prime = h / 60.0;
+    let x = c * (1.0 - (h_prime % 2.0 - 1.0).abs());
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+
+    (
+        ((r1 + m) * 255.0).round() as u8,
+        ((g1 + m) * 255.0).round() as u8,
+        ((b1 + m) * 255.0).round() as u8,
+    )
+}
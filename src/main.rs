@@ -1,9 +1,15 @@
+mod clustering;
+mod distance;
+mod geojson;
+mod routing;
+mod search;
+mod streaming;
+
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use clustering::{perform_clustering, Algorithm};
 use csv::{ReaderBuilder, WriterBuilder};
-use linfa::{Dataset, prelude::Transformer};
-use linfa_clustering::Dbscan;
-use ndarray::{Array1, Array2};
+use routing::{build_graph, compute_centroids, shortest_path, write_centroids_csv};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs::File;
@@ -19,6 +25,10 @@ struct Cli {
     #[arg(short, long)]
     output: PathBuf,
 
+    /// Output format
+    #[arg(long, value_enum, default_value = "csv")]
+    format: OutputFormat,
+
     /// Maximum distance between points in a cluster (in kilometers)
     #[arg(long, default_value = "1.0")]
     epsilon: f64,
@@ -26,6 +36,54 @@ struct Cli {
     /// Minimum number of points required to form a cluster
     #[arg(long, default_value = "5")]
     min_samples: usize,
+
+    /// Clustering algorithm to run
+    #[arg(long, value_enum, default_value = "dbscan")]
+    algorithm: Algorithm,
+
+    /// Maximum reachability distance (in kilometers) for OPTICS; unbounded if omitted
+    #[arg(long)]
+    max_epsilon: Option<f64>,
+
+    /// Number of clusters for KMeans
+    #[arg(long, default_value = "5")]
+    k: usize,
+
+    /// Memory-map the input and parse it field-by-field instead of loading every row into
+    /// memory, for inputs too large to fit in RAM
+    #[arg(long)]
+    streaming: bool,
+
+    /// Write each cluster's centroid (mean lat/lon) and member count to this CSV file
+    #[arg(long)]
+    centroids_out: Option<PathBuf>,
+
+    /// Print the shortest route between two cluster ids, in cluster hops and total distance
+    #[arg(long, num_args = 2, value_names = ["FROM", "TO"])]
+    route: Option<Vec<i32>>,
+
+    /// Only connect centroids within this distance (in kilometers) when routing
+    #[arg(long)]
+    max_hop_km: Option<f64>,
+
+    /// Fuzzy-match POI names against this query string instead of clustering
+    #[arg(long)]
+    find: Option<String>,
+
+    /// Column containing POI names to search (required with --find)
+    #[arg(long)]
+    name_column: Option<String>,
+
+    /// Number of best matches to print (with --find)
+    #[arg(long, default_value = "10")]
+    top_k: usize,
+}
+
+/// Output file format for clustering results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Geojson,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -43,29 +101,129 @@ struct LocationInput {
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    if let Some(query) = &cli.find {
+        return run_find(&cli, query);
+    }
+
+    if cli.streaming {
+        return run_streaming(&cli);
+    }
+
     println!("Reading CSV file: {:?}", cli.input);
     let locations = read_csv(&cli.input)?;
-    
+
     println!("Found {} locations", locations.len());
-    
+
     if locations.is_empty() {
         anyhow::bail!("No locations found in the input file");
     }
 
-    println!("Running DBSCAN clustering...");
-    let clusters = perform_clustering(&locations, cli.epsilon, cli.min_samples)?;
-    
+    println!("Running {} clustering...", cli.algorithm.label());
+    let points: Vec<(f64, f64)> = locations.iter().map(|(lat, lon, _)| (*lat, *lon)).collect();
+    let clusters = perform_clustering(&points, cli.algorithm, cli.epsilon, cli.min_samples, cli.max_epsilon, cli.k)?;
+
     println!("Writing results to: {:?}", cli.output);
-    write_csv(&cli.output, &locations, &clusters)?;
-    
+    write_output(cli.format, &cli.output, &locations, &clusters)?;
+
+    print_summary(&clusters);
+    post_process(&cli, &points, &clusters)?;
+
+    Ok(())
+}
+
+/// Runs the same pipeline as `main`, but via memory-mapped, field-level ingestion so the
+/// full set of rows and attributes never has to live in memory at once.
+fn run_streaming(cli: &Cli) -> Result<()> {
+    if cli.format == OutputFormat::Geojson {
+        anyhow::bail!("--format geojson is not supported together with --streaming yet");
+    }
+
+    println!("Reading CSV file (streaming): {:?}", cli.input);
+    let points = streaming::read_coordinates_mmap(&cli.input)?;
+
+    println!("Found {} locations", points.len());
+
+    if points.is_empty() {
+        anyhow::bail!("No locations found in the input file");
+    }
+
+    println!("Running {} clustering...", cli.algorithm.label());
+    let clusters = perform_clustering(&points, cli.algorithm, cli.epsilon, cli.min_samples, cli.max_epsilon, cli.k)?;
+
+    println!("Writing results to: {:?}", cli.output);
+    streaming::write_csv_streaming(&cli.input, &cli.output, &clusters)?;
+
+    print_summary(&clusters);
+    post_process(&cli, &points, &clusters)?;
+
+    Ok(())
+}
+
+/// Looks up the `--top-k` rows whose `--name-column` best matches `query`, instead of
+/// running clustering.
+fn run_find(cli: &Cli, query: &str) -> Result<()> {
+    let name_column = cli
+        .name_column
+        .as_deref()
+        .context("--name-column is required with --find")?;
+
+    println!("Searching {:?} for names matching {:?}", cli.input, query);
+    let matches = search::find_best_matches(&cli.input, name_column, query, cli.top_k)?;
+
+    if matches.is_empty() {
+        println!("No matches found");
+        return Ok(());
+    }
+
+    for m in &matches {
+        match m.cluster {
+            Some(cluster) => println!(
+                "{} (distance {}) at ({:.6}, {:.6}), cluster {}",
+                m.name, m.distance, m.lat, m.lon, cluster
+            ),
+            None => println!("{} (distance {}) at ({:.6}, {:.6})", m.name, m.distance, m.lat, m.lon),
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles the optional centroid/routing features shared by both ingestion paths.
+fn post_process(cli: &Cli, points: &[(f64, f64)], clusters: &[i32]) -> Result<()> {
+    if cli.centroids_out.is_none() && cli.route.is_none() {
+        return Ok(());
+    }
+
+    let centroids = compute_centroids(points, clusters);
+
+    if let Some(centroids_out) = &cli.centroids_out {
+        println!("Writing centroids to: {:?}", centroids_out);
+        write_centroids_csv(centroids_out, &centroids)?;
+    }
+
+    if let Some(route) = &cli.route {
+        let (from, to) = (route[0], route[1]);
+        let graph = build_graph(&centroids, cli.max_hop_km);
+
+        match shortest_path(&graph, from, to) {
+            Some((path, total_km)) => {
+                let hops = path.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(" -> ");
+                println!("Route {} -> {}: {} ({:.2} km)", from, to, hops, total_km);
+            }
+            None => println!("No route found from cluster {} to cluster {}", from, to),
+        }
+    }
+
+    Ok(())
+}
+
+fn print_summary(clusters: &[i32]) {
     let cluster_count = clusters.iter().max().unwrap_or(&-1) + 1;
     let noise_count = clusters.iter().filter(|&&c| c == -1).count();
-    
+
     println!("Clustering complete!");
     println!("Found {} clusters", cluster_count);
     println!("{} points classified as noise", noise_count);
-
-    Ok(())
 }
 
 fn read_csv(input_path: &PathBuf) -> Result<Vec<(f64, f64, HashMap<String, String>)>> {
@@ -118,32 +276,17 @@ fn find_coordinate_column(headers: &csv::StringRecord, possible_names: &[&str])
     );
 }
 
-fn perform_clustering(
-    locations: &[(f64, f64, HashMap<String, String>)], 
-    epsilon: f64, 
-    min_samples: usize
-) -> Result<Vec<i32>> {
-    if locations.len() < 2 {
-        return Ok(vec![-1; locations.len()]);
+/// Dispatches to the writer for `format`, keeping `main`'s output step format-agnostic.
+fn write_output(
+    format: OutputFormat,
+    output_path: &PathBuf,
+    locations: &[(f64, f64, HashMap<String, String>)],
+    clusters: &[i32],
+) -> Result<()> {
+    match format {
+        OutputFormat::Csv => write_csv(output_path, locations, clusters),
+        OutputFormat::Geojson => geojson::write_geojson(output_path, locations, clusters),
     }
-
-    let points: Array2<f64> = Array2::from_shape_vec(
-        (locations.len(), 2),
-        locations
-            .iter()
-            .flat_map(|(lat, lon, _)| vec![*lat, *lon])
-            .collect(),
-    )?;
-
-    let dataset = Dataset::new(points, Array1::<usize>::zeros(locations.len()));
-    
-    let clusters = Dbscan::params(min_samples)
-        .tolerance(epsilon / 111.0) // Convert km to approximate degrees
-        .transform(dataset)?;
-    
-    Ok(clusters.targets().iter()
-        .map(|&cluster| cluster.map(|c| c as i32).unwrap_or(-1))
-        .collect())
 }
 
 fn write_csv(
@@ -0,0 +1,145 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::Result;
+use csv::WriterBuilder;
+
+use crate::distance::haversine_km;
+
+/// A cluster's center of mass, used as the node of the routing graph.
+#[derive(Debug, Clone, Copy)]
+pub struct Centroid {
+    pub id: i32,
+    pub lat: f64,
+    pub lon: f64,
+    pub count: usize,
+}
+
+/// Computes each cluster's centroid (mean lat/lon) and member count. Noise points (`-1`)
+/// don't belong to a cluster and are excluded.
+pub fn compute_centroids(points: &[(f64, f64)], clusters: &[i32]) -> Vec<Centroid> {
+    let mut sums: HashMap<i32, (f64, f64, usize)> = HashMap::new();
+
+    for (&(lat, lon), &cluster) in points.iter().zip(clusters.iter()) {
+        if cluster < 0 {
+            continue;
+        }
+        let entry = sums.entry(cluster).or_insert((0.0, 0.0, 0));
+        entry.0 += lat;
+        entry.1 += lon;
+        entry.2 += 1;
+    }
+
+    let mut centroids: Vec<Centroid> = sums
+        .into_iter()
+        .map(|(id, (lat_sum, lon_sum, count))| Centroid {
+            id,
+            lat: lat_sum / count as f64,
+            lon: lon_sum / count as f64,
+            count,
+        })
+        .collect();
+    centroids.sort_by_key(|c| c.id);
+    centroids
+}
+
+pub fn write_centroids_csv(path: &Path, centroids: &[Centroid]) -> Result<()> {
+    let file = File::create(path)?;
+    let mut writer = WriterBuilder::new().from_writer(file);
+
+    writer.write_record(["cluster", "lat", "lon", "count"])?;
+    for c in centroids {
+        writer.write_record([c.id.to_string(), c.lat.to_string(), c.lon.to_string(), c.count.to_string()])?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Adjacency map keyed by cluster id, with haversine distance (km) as edge weight.
+pub type CentroidGraph = HashMap<i32, Vec<(i32, f64)>>;
+
+/// Builds a fully-connected graph over centroids, optionally dropping edges longer than
+/// `max_hop_km` so only "reachable" clusters are linked directly.
+pub fn build_graph(centroids: &[Centroid], max_hop_km: Option<f64>) -> CentroidGraph {
+    let mut graph: CentroidGraph = HashMap::new();
+
+    for a in centroids {
+        for b in centroids {
+            if a.id == b.id {
+                continue;
+            }
+            let d = haversine_km(a.lat, a.lon, b.lat, b.lon);
+            if max_hop_km.map_or(true, |max| d <= max) {
+                graph.entry(a.id).or_default().push((b.id, d));
+            }
+        }
+    }
+
+    graph
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeapEntry {
+    cost: f64,
+    node: i32,
+}
+
+impl Eq for HeapEntry {}
+
+impl Ord for HeapEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so `BinaryHeap`, which is a max-heap, pops the smallest cost first.
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+impl PartialOrd for HeapEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Runs Dijkstra over a centroid graph and returns the ordered list of cluster ids on the
+/// shortest path from `from` to `to`, plus its total distance. Returns `None` if `to` is
+/// unreachable from `from`.
+pub fn shortest_path(graph: &CentroidGraph, from: i32, to: i32) -> Option<(Vec<i32>, f64)> {
+    let mut dist: HashMap<i32, f64> = HashMap::new();
+    let mut prev: HashMap<i32, i32> = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+
+    dist.insert(from, 0.0);
+    frontier.push(HeapEntry { cost: 0.0, node: from });
+
+    while let Some(HeapEntry { cost, node }) = frontier.pop() {
+        if node == to {
+            break;
+        }
+        if cost > *dist.get(&node).unwrap_or(&f64::INFINITY) {
+            continue;
+        }
+
+        for &(next, weight) in graph.get(&node).map(Vec::as_slice).unwrap_or_default() {
+            let next_cost = cost + weight;
+            if next_cost < *dist.get(&next).unwrap_or(&f64::INFINITY) {
+                dist.insert(next, next_cost);
+                prev.insert(next, node);
+                frontier.push(HeapEntry { cost: next_cost, node: next });
+            }
+        }
+    }
+
+    let total = *dist.get(&to)?;
+
+    let mut path = vec![to];
+    let mut current = to;
+    while current != from {
+        current = *prev.get(&current)?;
+        path.push(current);
+    }
+    path.reverse();
+
+    Some((path, total))
+}
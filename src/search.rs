@@ -0,0 +1,165 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csv::ReaderBuilder;
+
+use crate::find_coordinate_column;
+
+/// One fuzzy-match hit against the query string.
+#[derive(Debug, Clone)]
+pub struct Match {
+    pub name: String,
+    pub distance: usize,
+    pub lat: f64,
+    pub lon: f64,
+    pub cluster: Option<i32>,
+}
+
+/// Wraps a [`Match`] so the bounded heap in [`find_best_matches`] can order by distance
+/// alone, without requiring `f64` (which isn't `Ord`) to participate in comparisons.
+struct RankedMatch(Match);
+
+impl PartialEq for RankedMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.distance == other.0.distance
+    }
+}
+
+impl Eq for RankedMatch {}
+
+impl PartialOrd for RankedMatch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RankedMatch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.distance.cmp(&other.0.distance)
+    }
+}
+
+/// Damerau-Levenshtein edit distance between `a` and `b`: the Levenshtein recurrence
+/// extended with an adjacent-transposition case.
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (la, lb) = (a.len(), b.len());
+
+    let mut dp = vec![vec![0usize; lb + 1]; la + 1];
+    for (i, row) in dp.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for j in 0..=lb {
+        dp[0][j] = j;
+    }
+
+    for i in 1..=la {
+        for j in 1..=lb {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            dp[i][j] = (dp[i - 1][j] + 1) // deletion
+                .min(dp[i][j - 1] + 1) // insertion
+                .min(dp[i - 1][j - 1] + cost); // substitution
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                dp[i][j] = dp[i][j].min(dp[i - 2][j - 2] + 1); // adjacent transposition
+            }
+        }
+    }
+
+    dp[la][lb]
+}
+
+/// Scans `input_path` for the `top_k` rows whose `name_column` best matches `query`,
+/// ranked by case-insensitive Damerau-Levenshtein distance. Keeps memory flat by holding
+/// only a bounded max-heap of candidates while streaming records.
+pub fn find_best_matches(input_path: &Path, name_column: &str, query: &str, top_k: usize) -> Result<Vec<Match>> {
+    let file = File::open(input_path)?;
+    let mut reader = ReaderBuilder::new().from_reader(file);
+    let headers = reader.headers()?.clone();
+
+    let name_idx = headers
+        .iter()
+        .position(|h| h.eq_ignore_ascii_case(name_column))
+        .with_context(|| {
+            format!(
+                "Could not find name column {:?}. Available headers: {:?}",
+                name_column,
+                headers.iter().collect::<Vec<_>>()
+            )
+        })?;
+
+    let lat_idx = find_coordinate_column(&headers, &["lat", "latitude", "Latitude", "LAT"]).ok();
+    let lon_idx = find_coordinate_column(
+        &headers,
+        &["lon", "lng", "long", "longitude", "Longitude", "LON", "LNG"],
+    )
+    .ok();
+    let cluster_idx = headers.iter().position(|h| h.eq_ignore_ascii_case("cluster"));
+
+    let query = query.to_lowercase();
+    let mut heap: BinaryHeap<RankedMatch> = BinaryHeap::new();
+
+    for result in reader.records() {
+        let record = result?;
+        let Some(name) = record.get(name_idx) else {
+            continue;
+        };
+
+        let distance = damerau_levenshtein(&name.to_lowercase(), &query);
+        let lat = lat_idx.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0.0);
+        let lon = lon_idx.and_then(|i| record.get(i)).and_then(|s| s.parse().ok()).unwrap_or(0.0);
+        let cluster = cluster_idx.and_then(|i| record.get(i)).and_then(|s| s.parse().ok());
+
+        let candidate = RankedMatch(Match {
+            name: name.to_string(),
+            distance,
+            lat,
+            lon,
+            cluster,
+        });
+
+        if heap.len() < top_k {
+            heap.push(candidate);
+        } else if heap.peek().is_some_and(|worst| candidate.0.distance < worst.0.distance) {
+            heap.pop();
+            heap.push(candidate);
+        }
+    }
+
+    let mut matches: Vec<Match> = heap.into_iter().map(|ranked| ranked.0).collect();
+    matches.sort_by_key(|m| m.distance);
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let cases = [
+            ("", "", 0),
+            ("abc", "abc", 0),
+            ("", "abc", 3),
+            ("abc", "", 3),
+            ("kitten", "sitting", 3),
+            ("ab", "ba", 1),          // adjacent transposition counts as a single edit
+            ("acbd", "abcd", 1),      // transposition inside a longer string
+            ("starbuks", "starbucks", 1),
+        ];
+
+        for (a, b, expected) in cases {
+            assert_eq!(damerau_levenshtein(a, b), expected, "distance({a:?}, {b:?})");
+        }
+    }
+
+    #[test]
+    fn is_symmetric() {
+        assert_eq!(damerau_levenshtein("flaw", "lawn"), damerau_levenshtein("lawn", "flaw"));
+    }
+}
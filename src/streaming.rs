@@ -0,0 +1,173 @@
+use std::fs::File;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use csv::{ReaderBuilder, WriterBuilder};
+use csv_core::{ReadFieldResult, Reader as CoreReader};
+use memmap::Mmap;
+
+use crate::find_coordinate_column;
+
+/// Reads just the latitude/longitude columns of `path` via a memory-mapped, field-level
+/// parse, so files larger than RAM can be clustered without loading every column of
+/// every row up front. Non-coordinate attributes are re-read lazily by
+/// [`write_csv_streaming`] when the output is written.
+pub fn read_coordinates_mmap(path: &Path) -> Result<Vec<(f64, f64)>> {
+    let file = File::open(path)?;
+    let mmap = unsafe { Mmap::map(&file)? };
+    let data: &[u8] = &mmap;
+
+    let mut rdr = CoreReader::new();
+    let mut pos = 0usize;
+    let mut field = Vec::new();
+
+    let mut headers = Vec::new();
+    loop {
+        let record_end = read_field(&mut rdr, &data[pos..], &mut field, &mut pos);
+        headers.push(String::from_utf8_lossy(&field).into_owned());
+        if record_end {
+            break;
+        }
+    }
+
+    let header_record = csv::StringRecord::from(headers);
+    let lat_idx = find_coordinate_column(&header_record, &["lat", "latitude", "Latitude", "LAT"])?;
+    let lon_idx = find_coordinate_column(
+        &header_record,
+        &["lon", "lng", "long", "longitude", "Longitude", "LON", "LNG"],
+    )?;
+
+    let mut points = Vec::new();
+    let mut col = 0usize;
+    let mut lat = None;
+    let mut lon = None;
+    let mut row = 0usize;
+
+    while pos < data.len() {
+        let record_end = read_field(&mut rdr, &data[pos..], &mut field, &mut pos);
+
+        if col == lat_idx {
+            let text = std::str::from_utf8(&field).context("Non-UTF8 latitude field")?;
+            lat = Some(
+                text.parse()
+                    .with_context(|| format!("Failed to parse latitude as float on row {row}"))?,
+            );
+        } else if col == lon_idx {
+            let text = std::str::from_utf8(&field).context("Non-UTF8 longitude field")?;
+            lon = Some(
+                text.parse()
+                    .with_context(|| format!("Failed to parse longitude as float on row {row}"))?,
+            );
+        }
+        col += 1;
+
+        if record_end {
+            // A row with fewer columns than the coordinate indices would otherwise be
+            // silently dropped here, desyncing `points` from the row-indexed re-read in
+            // `write_csv_streaming` and shifting every later cluster id by one. Fail loudly
+            // instead, matching how the non-streaming `read_csv` already errors on a
+            // missing coordinate field rather than skipping the row.
+            let (la, lo) = lat
+                .take()
+                .zip(lon.take())
+                .with_context(|| format!("Row {row} has fewer columns than the coordinate columns require"))?;
+            points.push((la, lo));
+            col = 0;
+            row += 1;
+        }
+    }
+
+    Ok(points)
+}
+
+/// Feeds `data` through `rdr` field by field until one field is complete, growing `out`
+/// across `OutputFull` results instead of truncating long fields. Advances `pos` by the
+/// number of bytes consumed and returns whether the field ended its record.
+fn read_field(rdr: &mut CoreReader, mut data: &[u8], out: &mut Vec<u8>, pos: &mut usize) -> bool {
+    out.clear();
+    let mut buf = [0u8; 4096];
+
+    loop {
+        let (result, consumed, written) = rdr.read_field(data, &mut buf);
+        *pos += consumed;
+        data = &data[consumed..];
+        out.extend_from_slice(&buf[..written]);
+
+        match result {
+            ReadFieldResult::Field { record_end } => return record_end,
+            ReadFieldResult::End => return true,
+            ReadFieldResult::OutputFull => continue,
+            ReadFieldResult::InputEmpty => return true,
+        }
+    }
+}
+
+/// Writes clustering results for a streamed run by re-reading `input_path` row by row and
+/// pairing each row with its cluster id, so the full set of attributes never has to live
+/// in memory at once. Costs a second pass over the input compared to [`crate::write_csv`].
+///
+/// Column order matches [`crate::write_csv`] (original headers sorted alphabetically, then
+/// `cluster`) so the output schema doesn't depend on whether `--streaming` was used.
+pub fn write_csv_streaming(input_path: &Path, output_path: &Path, clusters: &[i32]) -> Result<()> {
+    let input_file = File::open(input_path)?;
+    let mut reader = ReaderBuilder::new().from_reader(input_file);
+    let headers = reader.headers()?.clone();
+
+    let mut column_order: Vec<usize> = (0..headers.len()).collect();
+    column_order.sort_by(|&a, &b| headers[a].cmp(&headers[b]));
+
+    let output_file = File::create(output_path)?;
+    let mut writer = WriterBuilder::new().from_writer(output_file);
+
+    let mut out_headers: Vec<&str> = column_order.iter().map(|&i| &headers[i]).collect();
+    out_headers.push("cluster");
+    writer.write_record(&out_headers)?;
+
+    for (i, result) in reader.records().enumerate() {
+        let record = result?;
+        let mut out_record: Vec<&str> = column_order.iter().map(|&idx| record.get(idx).unwrap_or("")).collect();
+        let cluster = clusters.get(i).copied().unwrap_or(-1).to_string();
+        out_record.push(&cluster);
+        writer.write_record(&out_record)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    fn write_temp_csv(contents: &str) -> std::path::PathBuf {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_nanos();
+        let mut path = std::env::temp_dir();
+        path.push(format!("clusterpoi_streaming_test_{nanos}.csv"));
+
+        let mut file = File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn reads_coordinates_for_well_formed_rows() {
+        let path = write_temp_csv("lat,lon,name\n1.0,2.0,Cafe\n3.5,4.5,Park\n");
+
+        let points = read_coordinates_mmap(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(points, vec![(1.0, 2.0), (3.5, 4.5)]);
+    }
+
+    #[test]
+    fn errors_on_short_row_instead_of_desyncing() {
+        let path = write_temp_csv("lat,lon,name\n1.0,2.0,Cafe\n3.0\n");
+
+        let result = read_coordinates_mmap(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(result.is_err(), "a short row must be reported, not silently dropped");
+    }
+}